@@ -21,6 +21,8 @@ const PROPERTY: &str = "ea";
 const PROPOSAL: &str = "aa";
 const RECORD: &str = "ec";
 const RECORD_TYPE: &str = "ee";
+const LOT: &str = "ac";
+const TELEMETRY_ANCHOR: &str = "ca";
 
 pub fn get_supply_chain_prefix() -> String {
     let mut sha = Sha512::new();
@@ -51,6 +53,10 @@ pub fn make_record_type_address(type_name: &str) -> String {
     get_supply_chain_prefix() + &RECORD_TYPE + &hash(type_name, 62)
 }
 
+pub fn make_lot_address(lot_id: &str) -> String {
+    get_supply_chain_prefix() + &LOT + &hash(lot_id, 62)
+}
+
 pub fn make_property_address(record_id: &str, property_name: &str, page: u32) -> String {
     make_property_address_range(record_id) + &hash(property_name, 22) + &num_to_page_number(page)
 }
@@ -66,3 +72,15 @@ pub fn num_to_page_number(page: u32) -> String {
 pub fn make_proposal_address(record_id: &str, agent_id: &str) -> String {
     get_supply_chain_prefix() + PROPOSAL + &hash(record_id, 36) + &hash(agent_id, 26)
 }
+
+pub fn make_telemetry_anchor_address(
+    record_id: &str,
+    property_name: &str,
+    start_timestamp: u64,
+) -> String {
+    get_supply_chain_prefix()
+        + TELEMETRY_ANCHOR
+        + &hash(record_id, 36)
+        + &hash(property_name, 22)
+        + &hash(&start_timestamp.to_string(), 4)
+}
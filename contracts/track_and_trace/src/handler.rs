@@ -18,11 +18,15 @@ use protobuf::RepeatedField;
 
 use std::collections::HashMap;
 
+use grid_sdk::namespace;
+use grid_sdk::protocol;
 use grid_sdk::protos::track_and_trace_agent::{Agent, AgentContainer};
+use grid_sdk::protos::track_and_trace_lot::{Lot, LotContainer};
 use grid_sdk::protos::track_and_trace_payload::{
-    AnswerProposalAction, AnswerProposalAction_Response, CreateAgentAction, CreateProposalAction,
-    CreateRecordAction, CreateRecordTypeAction, FinalizeRecordAction, RevokeReporterAction,
-    SCPayload, SCPayload_Action, UpdatePropertiesAction,
+    AnchorTelemetryAction, AnswerProposalAction, AnswerProposalAction_Response, CreateAgentAction,
+    CreateLotAction, CreateProposalAction, CreateRecordAction, CreateRecordTypeAction,
+    FinalizeRecordAction, RevokeReporterAction, SCPayload, SCPayload_Action,
+    UpdatePropertiesAction,
 };
 use grid_sdk::protos::track_and_trace_property::{
     Property, PropertyContainer, PropertyPage, PropertyPageContainer, PropertyPage_ReportedValue,
@@ -34,6 +38,8 @@ use grid_sdk::protos::track_and_trace_proposal::{
 use grid_sdk::protos::track_and_trace_record::{
     Record, RecordContainer, RecordType, RecordTypeContainer, Record_AssociatedAgent,
 };
+use grid_sdk::protos::track_and_trace_telemetry::{TelemetryAnchor, TelemetryAnchorContainer};
+use grid_sdk::uom;
 use sawtooth_sdk::messages::processor::TpProcessRequest;
 use sawtooth_sdk::processor::handler::ApplyError;
 use sawtooth_sdk::processor::handler::TransactionContext;
@@ -42,6 +48,7 @@ use sawtooth_sdk::processor::handler::TransactionHandler;
 use addressing::*;
 
 const PROPERTY_PAGE_MAX_LENGTH: usize = 256;
+const MERKLE_ROOT_LENGTH: usize = 64;
 
 #[derive(Debug, Clone)]
 enum Action {
@@ -53,11 +60,14 @@ enum Action {
     CreateProposal(CreateProposalAction),
     AnswerProposal(AnswerProposalAction),
     RevokeReporter(RevokeReporterAction),
+    CreateLot(CreateLotAction),
+    AnchorTelemetry(AnchorTelemetryAction),
 }
 
 struct SupplyChainPayload {
     action: Action,
     timestamp: u64,
+    protocol_version: u32,
 }
 
 impl SupplyChainPayload {
@@ -71,6 +81,17 @@ impl SupplyChainPayload {
             }
         };
 
+        if !protocol::is_supported_track_and_trace_version(payload.get_protocol_version()) {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Unsupported protocol version {}; supported versions are {} to {}",
+                payload.get_protocol_version(),
+                protocol::TRACK_AND_TRACE_MIN_PROTOCOL_VERSION,
+                protocol::TRACK_AND_TRACE_PROTOCOL_VERSION
+            )));
+        }
+
+        let protocol_version = protocol::effective_version(payload.get_protocol_version());
+
         let supply_chain_action = payload.get_action();
         let action = match supply_chain_action {
             SCPayload_Action::CREATE_AGENT => {
@@ -113,6 +134,15 @@ impl SupplyChainPayload {
                             "Property name cannot be an empty string",
                         )));
                     }
+                    if protocol_version >= protocol::TRACK_AND_TRACE_UOM_VALIDATION_VERSION
+                        && prop.unit != ""
+                        && !uom::is_valid_unit_code(&prop.unit)
+                    {
+                        return Err(ApplyError::InvalidTransaction(format!(
+                            "Property {} has an unknown unit of measure: {}",
+                            prop.name, prop.unit
+                        )));
+                    }
                 }
 
                 Action::CreateRecordType(create_record_type.clone())
@@ -129,6 +159,67 @@ impl SupplyChainPayload {
             SCPayload_Action::REVOKE_REPORTER => {
                 Action::RevokeReporter(payload.get_revoke_reporter().clone())
             }
+            SCPayload_Action::CREATE_LOT => {
+                let create_lot = payload.get_create_lot();
+                if create_lot.get_lot_id() == "" {
+                    return Err(ApplyError::InvalidTransaction(String::from(
+                        "Lot id cannot be empty string",
+                    )));
+                }
+                if create_lot.get_product_id() == "" {
+                    return Err(ApplyError::InvalidTransaction(String::from(
+                        "Lot product id cannot be empty string",
+                    )));
+                }
+                let record_ids = create_lot.get_record_ids();
+                if record_ids.len() == 0 {
+                    return Err(ApplyError::InvalidTransaction(String::from(
+                        "Lot must contain at least one record",
+                    )));
+                }
+                for (index, record_id) in record_ids.iter().enumerate() {
+                    if record_ids[..index].contains(record_id) {
+                        return Err(ApplyError::InvalidTransaction(format!(
+                            "Lot contains duplicate record: {}",
+                            record_id
+                        )));
+                    }
+                }
+                Action::CreateLot(create_lot.clone())
+            }
+            SCPayload_Action::ANCHOR_TELEMETRY => {
+                let anchor = payload.get_anchor_telemetry();
+                if anchor.get_record_id() == "" {
+                    return Err(ApplyError::InvalidTransaction(String::from(
+                        "Record id cannot be empty string",
+                    )));
+                }
+                if anchor.get_property_name() == "" {
+                    return Err(ApplyError::InvalidTransaction(String::from(
+                        "Property name cannot be an empty string",
+                    )));
+                }
+                let merkle_root = anchor.get_merkle_root();
+                if merkle_root.len() != MERKLE_ROOT_LENGTH
+                    || !merkle_root.chars().all(|c| c.is_digit(16))
+                {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "Merkle root must be a {} character hex-encoded SHA-256 digest",
+                        MERKLE_ROOT_LENGTH
+                    )));
+                }
+                if anchor.get_start_timestamp() > anchor.get_end_timestamp() {
+                    return Err(ApplyError::InvalidTransaction(String::from(
+                        "Anchor start timestamp must not be after its end timestamp",
+                    )));
+                }
+                if anchor.get_reading_count() == 0 {
+                    return Err(ApplyError::InvalidTransaction(String::from(
+                        "Anchor must cover at least one reading",
+                    )));
+                }
+                Action::AnchorTelemetry(anchor.clone())
+            }
         };
         let timestamp = match payload.get_timestamp() {
             0 => {
@@ -142,6 +233,7 @@ impl SupplyChainPayload {
         Ok(Some(SupplyChainPayload {
             action: action,
             timestamp: timestamp,
+            protocol_version: protocol_version,
         }))
     }
 
@@ -152,20 +244,52 @@ impl SupplyChainPayload {
     pub fn get_timestamp(&self) -> u64 {
         self.timestamp
     }
+
+    pub fn get_protocol_version(&self) -> u32 {
+        self.protocol_version
+    }
 }
 
 pub struct SupplyChainState<'a> {
     context: &'a mut TransactionContext,
+    namespaces: Vec<String>,
 }
 
 impl<'a> SupplyChainState<'a> {
-    pub fn new(context: &'a mut TransactionContext) -> SupplyChainState {
-        SupplyChainState { context: context }
+    pub fn new(context: &'a mut TransactionContext, namespaces: Vec<String>) -> SupplyChainState {
+        SupplyChainState {
+            context: context,
+            namespaces: namespaces,
+        }
+    }
+
+    // A malformed address, or one outside of the namespaces the handler
+    // declares, indicates an addressing bug, so fail loudly in debug builds.
+    // Otherwise the transaction is rejected as invalid rather than failed
+    // with an InternalError, which the validator would retry forever; the
+    // same payload always derives the same addresses, so a retry can never
+    // succeed.
+    fn check_addresses<A: AsRef<str>>(&self, addresses: &[A]) -> Result<(), ApplyError> {
+        let result = namespace::check_addresses(addresses, &self.namespaces);
+        debug_assert!(result.is_ok(), "{}", result.as_ref().unwrap_err());
+        result.map_err(|err| ApplyError::InvalidTransaction(format!("{}", err)))
+    }
+
+    fn get_state_entry(&mut self, address: String) -> Result<Option<Vec<u8>>, ApplyError> {
+        self.check_addresses(&[&address])?;
+        Ok(self.context.get_state(vec![address])?)
+    }
+
+    fn set_state_entries(&mut self, sets: HashMap<String, Vec<u8>>) -> Result<(), ApplyError> {
+        self.check_addresses(&sets.keys().collect::<Vec<_>>())?;
+        self.context
+            .set_state(sets)
+            .map_err(|err| ApplyError::InternalError(format!("{}", err)))
     }
 
     pub fn get_record(&mut self, record_id: &str) -> Result<Option<Record>, ApplyError> {
         let address = make_record_address(record_id);
-        let d = self.context.get_state(vec![address])?;
+        let d = self.get_state_entry(address)?;
         match d {
             Some(packed) => {
                 let records: RecordContainer = match protobuf::parse_from_bytes(packed.as_slice()) {
@@ -190,7 +314,7 @@ impl<'a> SupplyChainState<'a> {
 
     pub fn set_record(&mut self, record_id: &str, record: Record) -> Result<(), ApplyError> {
         let address = make_record_address(record_id);
-        let d = self.context.get_state(vec![address.clone()])?;
+        let d = self.get_state_entry(address.clone())?;
         let mut record_container = match d {
             Some(packed) => match protobuf::parse_from_bytes(packed.as_slice()) {
                 Ok(records) => records,
@@ -234,15 +358,144 @@ impl<'a> SupplyChainState<'a> {
         };
         let mut sets = HashMap::new();
         sets.insert(address, serialized);
-        self.context
-            .set_state(sets)
-            .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
+        self.set_state_entries(sets)?;
+        Ok(())
+    }
+
+    pub fn get_lot(&mut self, lot_id: &str) -> Result<Option<Lot>, ApplyError> {
+        let address = make_lot_address(lot_id);
+        let d = self.get_state_entry(address)?;
+        match d {
+            Some(packed) => {
+                let lots: LotContainer = match protobuf::parse_from_bytes(packed.as_slice()) {
+                    Ok(lots) => lots,
+                    Err(_) => {
+                        return Err(ApplyError::InternalError(String::from(
+                            "Cannot deserialize lot container",
+                        )));
+                    }
+                };
+
+                for lot in lots.get_entries() {
+                    if lot.lot_id == lot_id {
+                        return Ok(Some(lot.clone()));
+                    }
+                }
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn set_lot(&mut self, lot_id: &str, lot: Lot) -> Result<(), ApplyError> {
+        let address = make_lot_address(lot_id);
+        let d = self.get_state_entry(address.clone())?;
+        let mut lots = match d {
+            Some(packed) => match protobuf::parse_from_bytes(packed.as_slice()) {
+                Ok(lots) => lots,
+                Err(_) => {
+                    return Err(ApplyError::InternalError(String::from(
+                        "Cannot deserialize lot container",
+                    )));
+                }
+            },
+            None => LotContainer::new(),
+        };
+
+        lots.entries.push(lot);
+        lots.entries.sort_by_key(|l| l.clone().lot_id);
+        let serialized = match lots.write_to_bytes() {
+            Ok(serialized) => serialized,
+            Err(_) => {
+                return Err(ApplyError::InternalError(String::from(
+                    "Cannot serialize lot container",
+                )));
+            }
+        };
+        let mut sets = HashMap::new();
+        sets.insert(address, serialized);
+        self.set_state_entries(sets)?;
+        Ok(())
+    }
+
+    pub fn get_telemetry_anchor(
+        &mut self,
+        record_id: &str,
+        property_name: &str,
+        start_timestamp: u64,
+    ) -> Result<Option<TelemetryAnchor>, ApplyError> {
+        let address = make_telemetry_anchor_address(record_id, property_name, start_timestamp);
+        let d = self.get_state_entry(address)?;
+        match d {
+            Some(packed) => {
+                let anchors: TelemetryAnchorContainer =
+                    match protobuf::parse_from_bytes(packed.as_slice()) {
+                        Ok(anchors) => anchors,
+                        Err(_) => {
+                            return Err(ApplyError::InternalError(String::from(
+                                "Cannot deserialize telemetry anchor container",
+                            )));
+                        }
+                    };
+
+                for anchor in anchors.get_entries() {
+                    if anchor.record_id == record_id
+                        && anchor.property_name == property_name
+                        && anchor.start_timestamp == start_timestamp
+                    {
+                        return Ok(Some(anchor.clone()));
+                    }
+                }
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn set_telemetry_anchor(&mut self, anchor: TelemetryAnchor) -> Result<(), ApplyError> {
+        let address = make_telemetry_anchor_address(
+            anchor.get_record_id(),
+            anchor.get_property_name(),
+            anchor.get_start_timestamp(),
+        );
+        let d = self.get_state_entry(address.clone())?;
+        let mut anchors = match d {
+            Some(packed) => match protobuf::parse_from_bytes(packed.as_slice()) {
+                Ok(anchors) => anchors,
+                Err(_) => {
+                    return Err(ApplyError::InternalError(String::from(
+                        "Cannot deserialize telemetry anchor container",
+                    )));
+                }
+            },
+            None => TelemetryAnchorContainer::new(),
+        };
+
+        anchors.entries.push(anchor);
+        anchors.entries.sort_by_key(|a| {
+            (
+                a.clone().record_id,
+                a.clone().property_name,
+                a.clone().start_timestamp,
+            )
+        });
+        let serialized = match anchors.write_to_bytes() {
+            Ok(serialized) => serialized,
+            Err(_) => {
+                return Err(ApplyError::InternalError(String::from(
+                    "Cannot serialize telemetry anchor container",
+                )));
+            }
+        };
+        let mut sets = HashMap::new();
+        sets.insert(address, serialized);
+        self.set_state_entries(sets)?;
         Ok(())
     }
 
     pub fn get_record_type(&mut self, type_name: &str) -> Result<Option<RecordType>, ApplyError> {
         let address = make_record_type_address(type_name);
-        let d = self.context.get_state(vec![address])?;
+        let d = self.get_state_entry(address)?;
         match d {
             Some(packed) => {
                 let record_types: RecordTypeContainer =
@@ -272,7 +525,7 @@ impl<'a> SupplyChainState<'a> {
         record_type: RecordType,
     ) -> Result<(), ApplyError> {
         let address = make_record_type_address(type_name);
-        let d = self.context.get_state(vec![address.clone()])?;
+        let d = self.get_state_entry(address.clone())?;
         let mut record_types = match d {
             Some(packed) => match protobuf::parse_from_bytes(packed.as_slice()) {
                 Ok(record_types) => record_types,
@@ -297,15 +550,13 @@ impl<'a> SupplyChainState<'a> {
         };
         let mut sets = HashMap::new();
         sets.insert(address, serialized);
-        self.context
-            .set_state(sets)
-            .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
+        self.set_state_entries(sets)?;
         Ok(())
     }
 
     pub fn get_agent(&mut self, agent_id: &str) -> Result<Option<Agent>, ApplyError> {
         let address = make_agent_address(agent_id);
-        let d = self.context.get_state(vec![address])?;
+        let d = self.get_state_entry(address)?;
         match d {
             Some(packed) => {
                 let agents: AgentContainer = match protobuf::parse_from_bytes(packed.as_slice()) {
@@ -330,7 +581,7 @@ impl<'a> SupplyChainState<'a> {
 
     pub fn set_agent(&mut self, agent_id: &str, agent: Agent) -> Result<(), ApplyError> {
         let address = make_agent_address(agent_id);
-        let d = self.context.get_state(vec![address.clone()])?;
+        let d = self.get_state_entry(address.clone())?;
         let mut agents = match d {
             Some(packed) => match protobuf::parse_from_bytes(packed.as_slice()) {
                 Ok(agents) => agents,
@@ -355,9 +606,7 @@ impl<'a> SupplyChainState<'a> {
         };
         let mut sets = HashMap::new();
         sets.insert(address, serialized);
-        self.context
-            .set_state(sets)
-            .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
+        self.set_state_entries(sets)?;
         Ok(())
     }
 
@@ -367,7 +616,7 @@ impl<'a> SupplyChainState<'a> {
         property_name: &str,
     ) -> Result<Option<Property>, ApplyError> {
         let address = make_property_address(record_id, property_name, 0);
-        let d = self.context.get_state(vec![address])?;
+        let d = self.get_state_entry(address)?;
         match d {
             Some(packed) => {
                 let properties: PropertyContainer =
@@ -398,7 +647,7 @@ impl<'a> SupplyChainState<'a> {
         property: Property,
     ) -> Result<(), ApplyError> {
         let address = make_property_address(record_id, property_name, 0);
-        let d = self.context.get_state(vec![address.clone()])?;
+        let d = self.get_state_entry(address.clone())?;
         let mut property_container = match d {
             Some(packed) => match protobuf::parse_from_bytes(packed.as_slice()) {
                 Ok(properties) => properties,
@@ -440,9 +689,7 @@ impl<'a> SupplyChainState<'a> {
         };
         let mut sets = HashMap::new();
         sets.insert(address, serialized);
-        self.context
-            .set_state(sets)
-            .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
+        self.set_state_entries(sets)?;
         Ok(())
     }
 
@@ -453,7 +700,7 @@ impl<'a> SupplyChainState<'a> {
         page: u32,
     ) -> Result<Option<PropertyPage>, ApplyError> {
         let address = make_property_address(record_id, property_name, page);
-        let d = self.context.get_state(vec![address])?;
+        let d = self.get_state_entry(address)?;
         match d {
             Some(packed) => {
                 let property_pages: PropertyPageContainer =
@@ -485,7 +732,7 @@ impl<'a> SupplyChainState<'a> {
         property_page: PropertyPage,
     ) -> Result<(), ApplyError> {
         let address = make_property_address(record_id, property_name, page_num);
-        let d = self.context.get_state(vec![address.clone()])?;
+        let d = self.get_state_entry(address.clone())?;
         let mut property_pages = match d {
             Some(packed) => match protobuf::parse_from_bytes(packed.as_slice()) {
                 Ok(property_pages) => property_pages,
@@ -527,9 +774,7 @@ impl<'a> SupplyChainState<'a> {
         };
         let mut sets = HashMap::new();
         sets.insert(address, serialized);
-        self.context
-            .set_state(sets)
-            .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
+        self.set_state_entries(sets)?;
         Ok(())
     }
 
@@ -539,7 +784,7 @@ impl<'a> SupplyChainState<'a> {
         agent_id: &str,
     ) -> Result<Option<ProposalContainer>, ApplyError> {
         let address = make_proposal_address(record_id, agent_id);
-        let d = self.context.get_state(vec![address])?;
+        let d = self.get_state_entry(address)?;
         match d {
             Some(packed) => {
                 let proposals: ProposalContainer =
@@ -575,9 +820,7 @@ impl<'a> SupplyChainState<'a> {
         };
         let mut sets = HashMap::new();
         sets.insert(address, serialized);
-        self.context
-            .set_state(sets)
-            .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
+        self.set_state_entries(sets)?;
         Ok(())
     }
 }
@@ -1135,6 +1378,7 @@ impl SupplyChainTransactionHandler {
         mut state: SupplyChainState,
         signer: &str,
         timestamp: u64,
+        protocol_version: u32,
     ) -> Result<(), ApplyError> {
         let record_id = payload.get_record_id();
         let receiving_agent = payload.get_receiving_agent();
@@ -1221,6 +1465,15 @@ impl SupplyChainTransactionHandler {
                     Err(err) => return Err(err),
                 };
 
+                if proposal_record.get_field_final()
+                    && protocol_version >= protocol::TRACK_AND_TRACE_FINAL_RECORD_ACCEPT_VERSION
+                {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "Record is final: {}",
+                        record_id
+                    )));
+                }
+
                 let owner = match proposal_record.clone().owners.last() {
                     Some(owner) => owner.clone(),
                     None => {
@@ -1345,6 +1598,14 @@ impl SupplyChainTransactionHandler {
                                 &receiving_agent,
                                 proposals.clone(),
                             )?;
+                            // Earlier protocol versions fall through and
+                            // transfer custody; keep doing so when replaying
+                            // them.
+                            if protocol_version
+                                >= protocol::TRACK_AND_TRACE_CUSTODY_ISSUER_CHECK_VERSION
+                            {
+                                return Ok(());
+                            }
                         }
 
                         let mut new_custodian = Record_AssociatedAgent::new();
@@ -1499,6 +1760,152 @@ impl SupplyChainTransactionHandler {
         Ok(())
     }
 
+    fn _create_lot(
+        &self,
+        payload: CreateLotAction,
+        mut state: SupplyChainState,
+        signer: &str,
+        timestamp: u64,
+    ) -> Result<(), ApplyError> {
+        match state.get_agent(signer) {
+            Ok(Some(_)) => (),
+            Ok(None) => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Agent is not registered: {}",
+                    signer
+                )));
+            }
+            Err(err) => return Err(err),
+        }
+        let lot_id = payload.get_lot_id();
+        match state.get_lot(lot_id) {
+            Ok(Some(_)) => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Lot already exists: {}",
+                    lot_id
+                )));
+            }
+            Ok(None) => (),
+            Err(err) => return Err(err),
+        }
+
+        for record_id in payload.get_record_ids() {
+            let lot_record = match state.get_record(record_id) {
+                Ok(Some(record)) => record,
+                Ok(None) => {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "Record does not exist: {}",
+                        record_id
+                    )));
+                }
+                Err(err) => return Err(err),
+            };
+            let owner = match lot_record.owners.last() {
+                Some(owner) => owner,
+                None => {
+                    return Err(ApplyError::InvalidTransaction(String::from(
+                        "Owner not found",
+                    )));
+                }
+            };
+            if owner.get_agent_id() != signer {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Must be owner of record to add it to a lot: {}",
+                    record_id
+                )));
+            }
+        }
+
+        let mut new_lot = Lot::new();
+        new_lot.set_lot_id(lot_id.to_string());
+        new_lot.set_product_id(payload.get_product_id().to_string());
+        new_lot.set_location_id(payload.get_location_id().to_string());
+        new_lot.set_record_ids(RepeatedField::from_vec(payload.get_record_ids().to_vec()));
+        new_lot.set_creator(signer.to_string());
+        new_lot.set_timestamp(timestamp);
+
+        state.set_lot(lot_id, new_lot)?;
+
+        Ok(())
+    }
+
+    fn _anchor_telemetry(
+        &self,
+        payload: AnchorTelemetryAction,
+        mut state: SupplyChainState,
+        signer: &str,
+        timestamp: u64,
+    ) -> Result<(), ApplyError> {
+        let record_id = payload.get_record_id();
+        let property_name = payload.get_property_name();
+        let start_timestamp = payload.get_start_timestamp();
+
+        let anchor_record = match state.get_record(record_id) {
+            Ok(Some(record)) => record,
+            Ok(None) => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Record does not exist: {}",
+                    record_id
+                )));
+            }
+            Err(err) => return Err(err),
+        };
+
+        if anchor_record.get_field_final() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Record is final: {}",
+                record_id
+            )));
+        }
+
+        let prop = match state.get_property(record_id, property_name) {
+            Ok(Some(prop)) => prop,
+            Ok(None) => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Record does not have provided property: {}",
+                    property_name
+                )));
+            }
+            Err(err) => return Err(err),
+        };
+
+        let allowed = prop
+            .get_reporters()
+            .iter()
+            .any(|reporter| reporter.get_public_key() == signer && reporter.get_authorized());
+        if !allowed {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Reporter is not authorized: {}",
+                signer
+            )));
+        }
+
+        match state.get_telemetry_anchor(record_id, property_name, start_timestamp) {
+            Ok(Some(_)) => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Telemetry anchor already exists for {} {} at {}",
+                    record_id, property_name, start_timestamp
+                )));
+            }
+            Ok(None) => (),
+            Err(err) => return Err(err),
+        }
+
+        let mut anchor = TelemetryAnchor::new();
+        anchor.set_record_id(record_id.to_string());
+        anchor.set_property_name(property_name.to_string());
+        anchor.set_merkle_root(payload.get_merkle_root().to_lowercase());
+        anchor.set_start_timestamp(start_timestamp);
+        anchor.set_end_timestamp(payload.get_end_timestamp());
+        anchor.set_reading_count(payload.get_reading_count());
+        anchor.set_reporter(signer.to_string());
+        anchor.set_timestamp(timestamp);
+
+        state.set_telemetry_anchor(anchor)?;
+
+        Ok(())
+    }
+
     fn _make_new_reported_value(
         &self,
         reporter_index: u32,
@@ -1625,63 +2032,88 @@ impl TransactionHandler for SupplyChainTransactionHandler {
         request: &TpProcessRequest,
         context: &mut TransactionContext,
     ) -> Result<(), ApplyError> {
-        let payload = SupplyChainPayload::new(request.get_payload());
-        let payload = match payload {
-            Err(e) => return Err(e),
-            Ok(payload) => payload,
+        let header = request.get_header();
+        let signer = header.get_signer_public_key();
+
+        let payload = match SupplyChainPayload::new(request.get_payload()) {
+            Ok(Some(payload)) => Ok(payload),
+            Ok(None) => Err(ApplyError::InvalidTransaction(String::from(
+                "Request must contain a payload",
+            ))),
+            Err(err) => Err(err),
         };
         let payload = match payload {
-            Some(x) => x,
-            None => {
-                return Err(ApplyError::InvalidTransaction(String::from(
-                    "Request must contain a payload",
-                )));
+            Ok(payload) => payload,
+            Err(err) => {
+                // The payload failed validation, so decode just its action
+                // again for the log; it may not even deserialize.
+                let action = match protobuf::parse_from_bytes::<SCPayload>(request.get_payload()) {
+                    Ok(payload) => format!("{:?}", payload.get_action()),
+                    Err(_) => String::from("unknown"),
+                };
+                debug!(
+                    "Rejected {} transaction signed by {}: {:?}",
+                    action, signer, err
+                );
+                return Err(err);
             }
         };
 
-        let signer = request.get_header().get_signer_public_key();
-        let state = SupplyChainState::new(context);
+        let state = SupplyChainState::new(context, self.namespaces());
 
-        info!(
-            "payload: {:?} {} {} {}",
+        debug!(
+            "action: {:?}, signer: {}, timestamp: {}, inputs: {:?}, outputs: {:?}",
             payload.get_action(),
+            signer,
             payload.get_timestamp(),
-            request.get_header().get_inputs()[0],
-            request.get_header().get_outputs()[0]
+            header.get_inputs(),
+            header.get_outputs()
         );
 
-        match payload.get_action() {
+        let result = match payload.get_action() {
             Action::CreateAgent(agent_payload) => {
-                self._create_agent(agent_payload, state, signer, payload.get_timestamp())?
+                self._create_agent(agent_payload, state, signer, payload.get_timestamp())
             }
             Action::CreateRecord(record_payload) => {
-                self._create_record(record_payload, state, signer, payload.get_timestamp())?
+                self._create_record(record_payload, state, signer, payload.get_timestamp())
             }
             Action::FinalizeRecord(finalize_payload) => {
-                self._finalize_record(finalize_payload, state, signer)?
+                self._finalize_record(finalize_payload, state, signer)
             }
             Action::CreateRecordType(record_type_payload) => {
-                self._create_record_type(record_type_payload, state, signer)?
+                self._create_record_type(record_type_payload, state, signer)
             }
             Action::UpdateProperties(update_properties_payload) => self._update_properties(
                 update_properties_payload,
                 state,
                 signer,
                 payload.get_timestamp(),
-            )?,
+            ),
             Action::CreateProposal(proposal_payload) => {
-                self._create_proposal(proposal_payload, state, signer, payload.get_timestamp())?
+                self._create_proposal(proposal_payload, state, signer, payload.get_timestamp())
             }
             Action::AnswerProposal(answer_proposal_payload) => self._answer_proposal(
                 answer_proposal_payload,
                 state,
                 signer,
                 payload.get_timestamp(),
-            )?,
+                payload.get_protocol_version(),
+            ),
             Action::RevokeReporter(revoke_reporter_payload) => {
-                self._revoke_reporter(revoke_reporter_payload, state, signer)?
+                self._revoke_reporter(revoke_reporter_payload, state, signer)
+            }
+            Action::CreateLot(lot_payload) => {
+                self._create_lot(lot_payload, state, signer, payload.get_timestamp())
             }
+            Action::AnchorTelemetry(anchor_payload) => {
+                self._anchor_telemetry(anchor_payload, state, signer, payload.get_timestamp())
+            }
+        };
+
+        match result {
+            Ok(()) => debug!("Applied transaction signed by {}", signer),
+            Err(ref err) => debug!("Rejected transaction signed by {}: {:?}", signer, err),
         }
-        Ok(())
+        result
     }
 }
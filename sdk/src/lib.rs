@@ -12,4 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod namespace;
+pub mod protocol;
 pub mod protos;
+pub mod uom;
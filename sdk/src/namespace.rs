@@ -0,0 +1,178 @@
+// Copyright 2019 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers for contracts to verify that the state addresses they read and
+//! write are well formed and fall within the namespaces they declare.
+
+use std::error::Error as StdError;
+
+/// The length, in hex characters, of a Sawtooth state address.
+pub const ADDRESS_LENGTH: usize = 70;
+
+#[derive(Debug)]
+pub enum NamespaceError {
+    MalformedAddress(String),
+    AddressOutsideNamespaces(String),
+}
+
+impl StdError for NamespaceError {
+    fn description(&self) -> &str {
+        match *self {
+            NamespaceError::MalformedAddress(ref msg) => msg,
+            NamespaceError::AddressOutsideNamespaces(ref msg) => msg,
+        }
+    }
+
+    fn cause(&self) -> Option<&StdError> {
+        match *self {
+            NamespaceError::MalformedAddress(_) => None,
+            NamespaceError::AddressOutsideNamespaces(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for NamespaceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            NamespaceError::MalformedAddress(ref s) => write!(f, "MalformedAddress: {}", s),
+            NamespaceError::AddressOutsideNamespaces(ref s) => {
+                write!(f, "AddressOutsideNamespaces: {}", s)
+            }
+        }
+    }
+}
+
+/// Returns true if the address is exactly `ADDRESS_LENGTH` lowercase hex
+/// characters.
+pub fn is_well_formed(address: &str) -> bool {
+    address.len() == ADDRESS_LENGTH
+        && address
+            .chars()
+            .all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase())
+}
+
+/// Returns true if the address starts with one of the given namespace
+/// prefixes.
+pub fn is_in_namespaces<S: AsRef<str>>(address: &str, namespaces: &[S]) -> bool {
+    namespaces
+        .iter()
+        .any(|namespace| address.starts_with(namespace.as_ref()))
+}
+
+/// Checks that every address is well formed and falls within one of the
+/// given namespaces.
+pub fn check_addresses<A, S>(addresses: &[A], namespaces: &[S]) -> Result<(), NamespaceError>
+where
+    A: AsRef<str>,
+    S: AsRef<str>,
+{
+    for address in addresses {
+        if !is_well_formed(address.as_ref()) {
+            return Err(NamespaceError::MalformedAddress(format!(
+                "Address {} is not {} lowercase hex characters",
+                address.as_ref(),
+                ADDRESS_LENGTH
+            )));
+        }
+        if !is_in_namespaces(address.as_ref(), namespaces) {
+            return Err(NamespaceError::AddressOutsideNamespaces(format!(
+                "Address {} is not in namespaces {:?}",
+                address.as_ref(),
+                namespaces.iter().map(|ns| ns.as_ref()).collect::<Vec<_>>()
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NAMESPACE: &str = "a43b46";
+
+    // Builds a well formed address from a prefix by padding it with zeros.
+    fn address(prefix: &str) -> String {
+        format!("{:0<70}", prefix)
+    }
+
+    #[test]
+    fn test_well_formed_address() {
+        assert!(is_well_formed(&address("a43b46ec")));
+        assert!(!is_well_formed(""));
+        assert!(!is_well_formed("a43b46ec0123"));
+        assert!(!is_well_formed(&format!("{}0", address("a43b46ec"))));
+        assert!(!is_well_formed(&address("A43B46EC")));
+        assert!(!is_well_formed(&address("a43b46ecxyz")));
+    }
+
+    #[test]
+    fn test_address_in_namespace() {
+        assert!(is_in_namespaces(&address("a43b46ec0123"), &[NAMESPACE]));
+        assert!(check_addresses(
+            &[address("a43b46ec0123"), address("a43b46ae4567")],
+            &[NAMESPACE]
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_address_outside_namespaces() {
+        assert!(!is_in_namespaces(&address("cad11dec0123"), &[NAMESPACE]));
+        match check_addresses(
+            &[address("a43b46ec0123"), address("cad11dec0123")],
+            &[NAMESPACE],
+        ) {
+            Err(NamespaceError::AddressOutsideNamespaces(msg)) => {
+                assert!(msg.contains(&address("cad11dec0123")))
+            }
+            res => panic!("Expected AddressOutsideNamespaces, got {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_malformed_address() {
+        // A namespace prefix check alone accepts all of these
+        let malformed = vec![
+            "a43b46ec0123".to_string(),
+            "a43b46ec".to_string(),
+            address("a43b46EC0123"),
+            address("a43b46ecg123"),
+        ];
+        for bad in malformed {
+            assert!(is_in_namespaces(&bad, &[NAMESPACE]));
+            match check_addresses(&[address("a43b46ec0123"), bad.clone()], &[NAMESPACE]) {
+                Err(NamespaceError::MalformedAddress(msg)) => assert!(msg.contains(&bad)),
+                res => panic!("Expected MalformedAddress for {}, got {:?}", bad, res),
+            }
+        }
+    }
+
+    #[test]
+    fn test_multiple_namespaces() {
+        let namespaces = vec![NAMESPACE.to_string(), "cad11d".to_string()];
+        assert!(check_addresses(
+            &[address("a43b46ec0123"), address("cad11dec0123")],
+            &namespaces
+        )
+        .is_ok());
+        assert!(check_addresses(&[address("621dee0123")], &namespaces).is_err());
+    }
+
+    #[test]
+    fn test_empty_address_list() {
+        let addresses: Vec<String> = vec![];
+        assert!(check_addresses(&addresses, &[NAMESPACE]).is_ok());
+    }
+}
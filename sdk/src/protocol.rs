@@ -0,0 +1,95 @@
+// Copyright 2019 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Payload protocol versions shared by clients and contracts.
+
+/// The newest track and trace payload protocol version contracts built
+/// against this SDK accept.
+pub const TRACK_AND_TRACE_PROTOCOL_VERSION: u32 = 3;
+
+/// The oldest track and trace payload protocol version contracts built against
+/// this SDK still accept.
+pub const TRACK_AND_TRACE_MIN_PROTOCOL_VERSION: u32 = 1;
+
+/// The first track and trace payload protocol version in which a property
+/// schema's unit must be a UN/CEFACT code known to the `uom` module. Earlier
+/// versions treat the unit as free-form metadata.
+pub const TRACK_AND_TRACE_UOM_VALIDATION_VERSION: u32 = 2;
+
+/// The first track and trace payload protocol version in which accepting a
+/// custodian proposal whose issuer is no longer the record's custodian only
+/// cancels the proposal. Earlier versions cancel it and then transfer custody
+/// anyway.
+pub const TRACK_AND_TRACE_CUSTODY_ISSUER_CHECK_VERSION: u32 = 3;
+
+/// The first track and trace payload protocol version in which a proposal
+/// for a finalized record can no longer be accepted.
+pub const TRACK_AND_TRACE_FINAL_RECORD_ACCEPT_VERSION: u32 = 3;
+
+/// Returns the effective protocol version of a payload. Payloads created
+/// before versioning was introduced leave the field unset (0) and are treated
+/// as version 1.
+pub fn effective_version(declared: u32) -> u32 {
+    match declared {
+        0 => 1,
+        version => version,
+    }
+}
+
+/// Returns true if a track and trace payload declaring the given protocol
+/// version can be handled.
+pub fn is_supported_track_and_trace_version(declared: u32) -> bool {
+    let version = effective_version(declared);
+    version >= TRACK_AND_TRACE_MIN_PROTOCOL_VERSION && version <= TRACK_AND_TRACE_PROTOCOL_VERSION
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unset_version_is_version_one() {
+        assert_eq!(effective_version(0), 1);
+        assert_eq!(effective_version(1), 1);
+        assert_eq!(effective_version(2), 2);
+    }
+
+    #[test]
+    fn test_supported_versions() {
+        assert!(is_supported_track_and_trace_version(0));
+        for version in TRACK_AND_TRACE_MIN_PROTOCOL_VERSION..=TRACK_AND_TRACE_PROTOCOL_VERSION {
+            assert!(is_supported_track_and_trace_version(version));
+        }
+    }
+
+    #[test]
+    fn test_unsupported_versions() {
+        assert!(!is_supported_track_and_trace_version(
+            TRACK_AND_TRACE_PROTOCOL_VERSION + 1
+        ));
+        assert!(!is_supported_track_and_trace_version(std::u32::MAX));
+    }
+
+    #[test]
+    fn test_gated_versions_are_supported() {
+        for version in &[
+            TRACK_AND_TRACE_UOM_VALIDATION_VERSION,
+            TRACK_AND_TRACE_CUSTODY_ISSUER_CHECK_VERSION,
+            TRACK_AND_TRACE_FINAL_RECORD_ACCEPT_VERSION,
+        ] {
+            assert!(*version >= TRACK_AND_TRACE_MIN_PROTOCOL_VERSION);
+            assert!(*version <= TRACK_AND_TRACE_PROTOCOL_VERSION);
+        }
+    }
+}
@@ -0,0 +1,217 @@
+// Copyright 2019 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A registry of commonly used UN/CEFACT (Recommendation 20) unit of measure
+//! codes, along with helpers to validate and convert between them.
+
+use std::error::Error as StdError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dimension {
+    Count,
+    Mass,
+    Length,
+    Area,
+    Volume,
+    Temperature,
+    Time,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnitOfMeasure {
+    /// The UN/CEFACT common code, e.g. "KGM"
+    pub code: &'static str,
+    pub name: &'static str,
+    pub dimension: Dimension,
+    // A value in this unit is converted to the base unit of its dimension
+    // with `value * factor + offset`.
+    factor: f64,
+    offset: f64,
+}
+
+macro_rules! unit {
+    ($code:expr, $name:expr, $dimension:ident, $factor:expr) => {
+        unit!($code, $name, $dimension, $factor, 0.0)
+    };
+    ($code:expr, $name:expr, $dimension:ident, $factor:expr, $offset:expr) => {
+        UnitOfMeasure {
+            code: $code,
+            name: $name,
+            dimension: Dimension::$dimension,
+            factor: $factor,
+            offset: $offset,
+        }
+    };
+}
+
+const UNITS: &[UnitOfMeasure] = &[
+    // Count; base unit is one
+    unit!("C62", "one", Count, 1.0),
+    unit!("H87", "piece", Count, 1.0),
+    unit!("DZN", "dozen", Count, 12.0),
+    // Mass; base unit is the kilogram
+    unit!("KGM", "kilogram", Mass, 1.0),
+    unit!("GRM", "gram", Mass, 0.001),
+    unit!("MGM", "milligram", Mass, 0.000_001),
+    unit!("TNE", "tonne", Mass, 1000.0),
+    unit!("LBR", "pound", Mass, 0.453_592_37),
+    unit!("ONZ", "ounce", Mass, 0.028_349_523_125),
+    // Length; base unit is the metre
+    unit!("MTR", "metre", Length, 1.0),
+    unit!("KMT", "kilometre", Length, 1000.0),
+    unit!("CMT", "centimetre", Length, 0.01),
+    unit!("MMT", "millimetre", Length, 0.001),
+    unit!("INH", "inch", Length, 0.0254),
+    unit!("FOT", "foot", Length, 0.3048),
+    unit!("YRD", "yard", Length, 0.9144),
+    // Area; base unit is the square metre
+    unit!("MTK", "square metre", Area, 1.0),
+    unit!("CMK", "square centimetre", Area, 0.0001),
+    unit!("FTK", "square foot", Area, 0.092_903_04),
+    // Volume; base unit is the litre
+    unit!("LTR", "litre", Volume, 1.0),
+    unit!("MLT", "millilitre", Volume, 0.001),
+    unit!("MTQ", "cubic metre", Volume, 1000.0),
+    unit!("GLL", "gallon (US)", Volume, 3.785_411_784),
+    // Temperature; base unit is the kelvin
+    unit!("KEL", "kelvin", Temperature, 1.0),
+    unit!("CEL", "degree Celsius", Temperature, 1.0, 273.15),
+    unit!(
+        "FAH",
+        "degree Fahrenheit",
+        Temperature,
+        5.0 / 9.0,
+        459.67 * 5.0 / 9.0
+    ),
+    // Time; base unit is the second
+    unit!("SEC", "second", Time, 1.0),
+    unit!("MIN", "minute", Time, 60.0),
+    unit!("HUR", "hour", Time, 3600.0),
+    unit!("DAY", "day", Time, 86400.0),
+];
+
+#[derive(Debug)]
+pub enum UomError {
+    UnknownUnit(String),
+    IncompatibleUnits(String),
+}
+
+impl StdError for UomError {
+    fn description(&self) -> &str {
+        match *self {
+            UomError::UnknownUnit(ref msg) => msg,
+            UomError::IncompatibleUnits(ref msg) => msg,
+        }
+    }
+
+    fn cause(&self) -> Option<&StdError> {
+        match *self {
+            UomError::UnknownUnit(_) => None,
+            UomError::IncompatibleUnits(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for UomError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            UomError::UnknownUnit(ref s) => write!(f, "UnknownUnit: {}", s),
+            UomError::IncompatibleUnits(ref s) => write!(f, "IncompatibleUnits: {}", s),
+        }
+    }
+}
+
+/// Returns the unit registered under the given UN/CEFACT code, if any.
+pub fn lookup(code: &str) -> Option<&'static UnitOfMeasure> {
+    UNITS.iter().find(|unit| unit.code == code)
+}
+
+/// Returns true if the code is a registered UN/CEFACT unit code.
+pub fn is_valid_unit_code(code: &str) -> bool {
+    lookup(code).is_some()
+}
+
+/// Converts a value between two units of the same dimension.
+pub fn convert(value: f64, from: &str, to: &str) -> Result<f64, UomError> {
+    let from_unit = lookup(from).ok_or_else(|| UomError::UnknownUnit(from.to_string()))?;
+    let to_unit = lookup(to).ok_or_else(|| UomError::UnknownUnit(to.to_string()))?;
+
+    if from_unit.dimension != to_unit.dimension {
+        return Err(UomError::IncompatibleUnits(format!(
+            "Cannot convert {:?} ({}) to {:?} ({})",
+            from_unit.dimension, from, to_unit.dimension, to
+        )));
+    }
+
+    let base = value * from_unit.factor + from_unit.offset;
+    Ok((base - to_unit.offset) / to_unit.factor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "{} is not close to {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_unit_codes_are_case_sensitive() {
+        assert!(is_valid_unit_code("KGM"));
+        assert_eq!(lookup("KGM").unwrap().dimension, Dimension::Mass);
+        assert!(!is_valid_unit_code("kgm"));
+        assert!(lookup("kgm").is_none());
+        assert!(!is_valid_unit_code(""));
+        assert!(lookup("").is_none());
+    }
+
+    #[test]
+    fn test_convert_scale() {
+        assert_close(convert(10.0, "LBR", "KGM").unwrap(), 4.535_923_7);
+        assert_close(convert(4.535_923_7, "KGM", "LBR").unwrap(), 10.0);
+    }
+
+    #[test]
+    fn test_convert_temperature() {
+        assert_close(convert(100.0, "CEL", "FAH").unwrap(), 212.0);
+        assert_close(convert(212.0, "FAH", "CEL").unwrap(), 100.0);
+        assert_close(convert(-40.0, "CEL", "FAH").unwrap(), -40.0);
+        assert_close(convert(0.0, "CEL", "KEL").unwrap(), 273.15);
+    }
+
+    #[test]
+    fn test_convert_unknown_unit() {
+        match convert(1.0, "kg", "KGM") {
+            Err(UomError::UnknownUnit(code)) => assert_eq!(code, "kg"),
+            res => panic!("Expected UnknownUnit, got {:?}", res),
+        }
+        match convert(1.0, "KGM", "XYZ") {
+            Err(UomError::UnknownUnit(code)) => assert_eq!(code, "XYZ"),
+            res => panic!("Expected UnknownUnit, got {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_convert_incompatible_units() {
+        match convert(1.0, "KGM", "MTR") {
+            Err(UomError::IncompatibleUnits(_)) => (),
+            res => panic!("Expected IncompatibleUnits, got {:?}", res),
+        }
+    }
+}